@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Resolve a GitHub token to authenticate API requests with, for the
+/// given host (`github.com` unless `--host`/`GH_HOST` points elsewhere).
+///
+/// Checked in order: an explicit `--token` flag, the `GITHUB_TOKEN` and
+/// `GH_TOKEN` environment variables, `gh auth token --hostname <host>`
+/// (if the `gh` CLI is installed and logged in to that host), and
+/// finally the `oauth_token` stored under that host in
+/// `~/.config/gh/hosts.yml`. Returns `None` if none of these produce a
+/// token, in which case requests are sent unauthenticated.
+pub fn resolve_token(cli_token: Option<&String>, host: &str) -> Option<String> {
+    if let Some(token) = cli_token {
+        return Some(token.clone());
+    }
+
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    if let Ok(token) = std::env::var("GH_TOKEN") {
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    if let Some(token) = token_from_gh_cli(host) {
+        return Some(token);
+    }
+
+    token_from_gh_hosts_file(host)
+}
+
+fn token_from_gh_cli(host: &str) -> Option<String> {
+    let output = Command::new("gh")
+        .args(["auth", "token", "--hostname", host])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8(output.stdout).ok()?;
+    let token = token.trim();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_string())
+    }
+}
+
+fn token_from_gh_hosts_file(host: &str) -> Option<String> {
+    let path = gh_hosts_path()?;
+    let contents = fs::read_to_string(path).ok()?;
+    parse_oauth_token_from_hosts_yaml(&contents, host)
+}
+
+fn gh_hosts_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("GH_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs_config_dir().map(|dir| dir.join("gh")))?;
+    Some(config_dir.join("hosts.yml"))
+}
+
+fn dirs_config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Minimal line-based parse of `hosts.yml`, good enough to pull
+/// `oauth_token:` out from under a `github.com:` (or other host) key
+/// without pulling in a full YAML parser for one value.
+fn parse_oauth_token_from_hosts_yaml(contents: &str, host: &str) -> Option<String> {
+    let host_header = format!("{}:", host);
+    let mut in_host_block = false;
+    for line in contents.lines() {
+        if !line.starts_with(' ') && !line.starts_with('\t') {
+            in_host_block = line.trim_end() == host_header;
+            continue;
+        }
+        if in_host_block {
+            let trimmed = line.trim();
+            if let Some(value) = trimmed.strip_prefix("oauth_token:") {
+                let value = value.trim().trim_matches('"').trim_matches('\'');
+                if !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+    None
+}
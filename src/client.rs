@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Result};
+use reqwest::{Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+/// Outcome of fetching every page of a paginated endpoint (optionally
+/// capped).
+pub struct PagedResult<T> {
+    pub items: Vec<T>,
+    /// True if there were more results than `items` contains, because a
+    /// `--max-comments`-style cap was hit.
+    pub truncated: bool,
+}
+
+/// Thin wrapper around `reqwest::Client` that attaches the headers every
+/// GitHub API request needs (auth, `Accept`, `User-Agent`) and turns
+/// common failure responses into actionable errors instead of letting
+/// callers hit a JSON-parse error on an HTML/empty body.
+pub struct GitHubClient {
+    inner: Client,
+    token: Option<String>,
+}
+
+impl GitHubClient {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            inner: Client::new(),
+            token,
+        }
+    }
+
+    /// Perform a GET request and return the raw response after checking
+    /// it for auth/rate-limit/not-found problems.
+    pub async fn get(&self, url: &str) -> Result<Response> {
+        let mut request = self
+            .inner
+            .get(url)
+            .header("User-Agent", "gh-pr-comments")
+            .header("Accept", "application/vnd.github+json");
+
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request.send().await?;
+        self.check_response(response).await
+    }
+
+    /// Fetch every page of a `Link`-header-paginated REST endpoint,
+    /// following `rel="next"` until it runs out or `max_items` is hit.
+    pub async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        max_items: Option<usize>,
+    ) -> Result<PagedResult<T>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut next_url = Some(format!("{}{}per_page=100", url, separator));
+        let mut items: Vec<T> = Vec::new();
+        let mut truncated = false;
+
+        while let Some(current_url) = next_url {
+            let response = self.get(&current_url).await?;
+            let next_link = response
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_next_link);
+
+            let mut page: Vec<T> = response.json().await?;
+            items.append(&mut page);
+
+            if let Some(max) = max_items {
+                if items.len() >= max {
+                    let had_more = items.len() > max;
+                    items.truncate(max);
+                    truncated = truncated || had_more || next_link.is_some();
+                    break;
+                }
+            }
+
+            next_url = next_link;
+        }
+
+        Ok(PagedResult { items, truncated })
+    }
+
+    /// Perform a POST request with a JSON body, used for the GraphQL
+    /// endpoint. Subject to the same error handling as `get`.
+    pub async fn post_json(&self, url: &str, body: &serde_json::Value) -> Result<Response> {
+        let mut request = self
+            .inner
+            .post(url)
+            .header("User-Agent", "gh-pr-comments")
+            .header("Accept", "application/vnd.github+json")
+            .json(body);
+
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request.send().await?;
+        self.check_response(response).await
+    }
+
+    async fn check_response(&self, response: Response) -> Result<Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        if matches!(status, StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) {
+            if let Some(wait) = rate_limit_wait(&response) {
+                return Err(anyhow!(
+                    "GitHub API rate limit exceeded; resets in {}",
+                    wait
+                ));
+            }
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED => Err(anyhow!(
+                "Not authenticated: GitHub rejected the request credentials. \
+                 Pass --token, set GITHUB_TOKEN/GH_TOKEN, or run `gh auth login`."
+            )),
+            StatusCode::FORBIDDEN if self.token.is_none() => Err(anyhow!(
+                "Not authenticated: this request requires a token (private repo or \
+                 low rate limit). Pass --token, set GITHUB_TOKEN/GH_TOKEN, or run `gh auth login`."
+            )),
+            StatusCode::NOT_FOUND => Err(anyhow!(
+                "Not found: the repository, PR, or resource does not exist, or your \
+                 token lacks access to it."
+            )),
+            status => {
+                let url = response.url().clone();
+                let body = response.text().await.unwrap_or_default();
+                Err(anyhow!(
+                    "GitHub API request to {} failed with status {}: {}",
+                    url,
+                    status,
+                    body
+                ))
+            }
+        }
+    }
+}
+
+/// Pull the `rel="next"` URL out of a GitHub `Link` response header, e.g.
+/// `<https://api.github.com/...&page=2>; rel="next", <...>; rel="last"`.
+fn parse_next_link(link_header: &str) -> Option<String> {
+    for part in link_header.split(',') {
+        let mut segments = part.split(';');
+        let url_part = segments.next()?.trim();
+        let is_next = segments.any(|seg| seg.trim() == r#"rel="next""#);
+        if is_next {
+            return Some(url_part.trim_start_matches('<').trim_end_matches('>').to_string());
+        }
+    }
+    None
+}
+
+/// If the response indicates the rate limit has been exhausted, format a
+/// human-readable "how long until it resets" message.
+fn rate_limit_wait(response: &Response) -> Option<String> {
+    let remaining = response
+        .headers()
+        .get("x-ratelimit-remaining")?
+        .to_str()
+        .ok()?;
+    if remaining != "0" {
+        return None;
+    }
+
+    let reset = response
+        .headers()
+        .get("x-ratelimit-reset")?
+        .to_str()
+        .ok()?
+        .parse::<i64>()
+        .ok()?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64;
+    let wait_secs = (reset - now).max(0);
+    let minutes = wait_secs / 60;
+    let seconds = wait_secs % 60;
+    Some(format!("{}m{}s", minutes, seconds))
+}
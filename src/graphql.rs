@@ -0,0 +1,207 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::client::GitHubClient;
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!, $cursor: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $cursor) {
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+        nodes {
+          isResolved
+          isOutdated
+          comments(first: 100) {
+            nodes {
+              databaseId
+              body
+              author {
+                login
+              }
+              url
+              diffHunk
+              path
+              line
+              createdAt
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Resolution status of a single review thread, and the ids (in thread
+/// order) of the comments that belong to it. Only `isResolved`/`isOutdated`
+/// and the comment ids are used today; the rest of the queried fields
+/// mirror what `comments(first: 100)` exposes so the query can grow into
+/// rendering review-thread content directly without another round trip.
+#[derive(Debug)]
+pub struct ReviewThread {
+    pub is_resolved: bool,
+    pub is_outdated: bool,
+    pub comment_ids: Vec<u64>,
+}
+
+/// Per-comment view of the thread it belongs to: its resolution status
+/// and its position within the thread's comment list (0 = the thread's
+/// root comment, everything after is a reply).
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadStatus {
+    pub thread_index: usize,
+    pub position_in_thread: usize,
+    pub is_resolved: bool,
+    pub is_outdated: bool,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<GraphQlData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlData {
+    repository: Option<RepositoryData>,
+}
+
+#[derive(Deserialize)]
+struct RepositoryData {
+    #[serde(rename = "pullRequest")]
+    pull_request: Option<PullRequestData>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestData {
+    #[serde(rename = "reviewThreads")]
+    review_threads: ReviewThreadsData,
+}
+
+#[derive(Deserialize)]
+struct ReviewThreadsData {
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+    nodes: Vec<ReviewThreadNode>,
+}
+
+#[derive(Deserialize)]
+struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReviewThreadNode {
+    #[serde(rename = "isResolved")]
+    is_resolved: bool,
+    #[serde(rename = "isOutdated")]
+    is_outdated: bool,
+    comments: ThreadComments,
+}
+
+#[derive(Deserialize)]
+struct ThreadComments {
+    nodes: Vec<ThreadComment>,
+}
+
+#[derive(Deserialize)]
+struct ThreadComment {
+    #[serde(rename = "databaseId")]
+    database_id: Option<u64>,
+}
+
+/// Fetch every review thread on a PR, paginating on `endCursor`, and
+/// return all of them in thread order.
+pub async fn fetch_review_threads(
+    client: &GitHubClient,
+    graphql_url: &str,
+    owner: &str,
+    name: &str,
+    pr_number: u32,
+) -> Result<Vec<ReviewThread>> {
+    let mut threads = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let body = json!({
+            "query": REVIEW_THREADS_QUERY,
+            "variables": {
+                "owner": owner,
+                "name": name,
+                "number": pr_number,
+                "cursor": cursor,
+            },
+        });
+
+        let response: GraphQlResponse = client.post_json(graphql_url, &body).await?.json().await?;
+
+        if let Some(error) = response.errors.first() {
+            return Err(anyhow::anyhow!("GitHub GraphQL error: {}", error.message));
+        }
+
+        let review_threads = response
+            .data
+            .and_then(|d| d.repository)
+            .and_then(|r| r.pull_request)
+            .map(|pr| pr.review_threads)
+            .ok_or_else(|| anyhow::anyhow!("PR #{} not found via GraphQL", pr_number))?;
+
+        let has_next_page = review_threads.page_info.has_next_page;
+        cursor = review_threads.page_info.end_cursor;
+
+        for node in review_threads.nodes {
+            threads.push(ReviewThread {
+                is_resolved: node.is_resolved,
+                is_outdated: node.is_outdated,
+                comment_ids: node
+                    .comments
+                    .nodes
+                    .into_iter()
+                    .filter_map(|c| c.database_id)
+                    .collect(),
+            });
+        }
+
+        if !has_next_page {
+            break;
+        }
+    }
+
+    Ok(threads)
+}
+
+/// Build a lookup from REST comment id to the thread it belongs to, so
+/// callers can filter by resolution and group replies under their root
+/// comment.
+pub fn index_by_comment_id(threads: &[ReviewThread]) -> HashMap<u64, ThreadStatus> {
+    let mut index = HashMap::new();
+    for (thread_index, thread) in threads.iter().enumerate() {
+        for (position_in_thread, comment_id) in thread.comment_ids.iter().enumerate() {
+            index.insert(
+                *comment_id,
+                ThreadStatus {
+                    thread_index,
+                    position_in_thread,
+                    is_resolved: thread.is_resolved,
+                    is_outdated: thread.is_outdated,
+                },
+            );
+        }
+    }
+    index
+}
@@ -0,0 +1,75 @@
+/// A forge that speaks (a dialect of) the GitHub REST/GraphQL API.
+///
+/// Implementations know how to turn a bare hostname into the URLs the
+/// rest of the crate needs to call, so nothing else in the codebase has
+/// to hardcode `api.github.com`.
+pub trait HostingProvider: std::fmt::Debug {
+    /// The hostname this provider serves, e.g. `github.com` or
+    /// `ghe.corp.com`.
+    fn host(&self) -> &str;
+
+    /// Base URL for REST API calls, e.g. `https://api.github.com` or
+    /// `https://ghe.corp.com/api/v3`.
+    fn api_base_url(&self) -> String;
+
+    /// URL for GraphQL API calls, e.g. `https://api.github.com/graphql`
+    /// or `https://ghe.corp.com/api/graphql`.
+    fn graphql_url(&self) -> String;
+}
+
+#[derive(Debug)]
+pub struct GitHubDotCom;
+
+impl HostingProvider for GitHubDotCom {
+    fn host(&self) -> &str {
+        "github.com"
+    }
+
+    fn api_base_url(&self) -> String {
+        "https://api.github.com".to_string()
+    }
+
+    fn graphql_url(&self) -> String {
+        "https://api.github.com/graphql".to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct GitHubEnterprise {
+    pub host: String,
+}
+
+impl HostingProvider for GitHubEnterprise {
+    fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn api_base_url(&self) -> String {
+        format!("https://{}/api/v3", self.host)
+    }
+
+    fn graphql_url(&self) -> String {
+        format!("https://{}/api/graphql", self.host)
+    }
+}
+
+/// Resolve a hostname (from `--host`/`GH_HOST`, a git remote, or a PR
+/// URL) to the provider that knows how to talk to it.
+pub fn provider_for_host(host: &str) -> Box<dyn HostingProvider> {
+    if host.eq_ignore_ascii_case("github.com") {
+        Box::new(GitHubDotCom)
+    } else {
+        Box::new(GitHubEnterprise {
+            host: host.to_string(),
+        })
+    }
+}
+
+/// The `--host`/`GH_HOST` override, if any, read from the CLI flag or
+/// environment variable (in that order).
+pub fn host_override(cli_host: Option<&String>) -> Option<String> {
+    if let Some(host) = cli_host {
+        return Some(host.clone());
+    }
+    std::env::var("GH_HOST").ok().filter(|h| !h.is_empty())
+}
@@ -2,10 +2,19 @@ use anyhow::{Context, Result};
 use clap::{Arg, Command};
 use git2::Repository;
 use regex::Regex;
-use reqwest::Client;
 use serde::Deserialize;
 use url::Url;
 
+mod auth;
+mod client;
+mod graphql;
+mod host;
+mod timeline;
+
+use client::GitHubClient;
+use host::HostingProvider;
+use timeline::{IssueComment, Review};
+
 #[derive(Debug, Deserialize)]
 struct PullRequest {
     title: String,
@@ -13,25 +22,42 @@ struct PullRequest {
 }
 
 #[derive(Debug, Deserialize)]
-struct Comment {
-    body: String,
-    user: User,
-    created_at: String,
-    html_url: String,
-    diff_hunk: String,
-    path: String,
-    line: Option<u32>,
+struct PullRequestSummary {
+    number: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoDetails {
+    parent: Option<ParentRepo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParentRepo {
+    full_name: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct User {
-    login: String,
+pub(crate) struct Comment {
+    pub(crate) id: u64,
+    pub(crate) body: String,
+    pub(crate) user: User,
+    pub(crate) created_at: String,
+    pub(crate) html_url: String,
+    pub(crate) diff_hunk: String,
+    pub(crate) path: String,
+    pub(crate) line: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct User {
+    pub(crate) login: String,
 }
 
 #[derive(Debug)]
 struct RepoInfo {
     owner: String,
     name: String,
+    provider: Box<dyn HostingProvider>,
 }
 
 #[tokio::main]
@@ -40,8 +66,11 @@ async fn main() -> Result<()> {
         .about("Extract GitHub PR comments as markdown for LLM consumption")
         .arg(
             Arg::new("pr")
-                .help("PR number, PR URL, or 'repo/pr_number' format")
-                .required(true)
+                .help(
+                    "PR number, PR URL, or 'repo/pr_number' format. \
+                     If omitted, the open PR for the current branch is used.",
+                )
+                .required(false)
                 .index(1),
         )
         .arg(
@@ -56,31 +85,80 @@ async fn main() -> Result<()> {
                 .long("include-resolved")
                 .help("Include resolved comments in output")
                 .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .help("GitHub token to authenticate with (defaults to GITHUB_TOKEN/GH_TOKEN or `gh auth token`)")
+                .value_name("TOKEN"),
+        )
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .help("GitHub host to talk to, for GitHub Enterprise (defaults to GH_HOST or github.com)")
+                .value_name("HOST"),
+        )
+        .arg(
+            Arg::new("max-comments")
+                .long("max-comments")
+                .help("Cap the number of comments fetched, useful for bounding LLM context")
+                .value_name("N"),
         );
 
     let matches = app.get_matches();
-    let pr_input = matches.get_one::<String>("pr").unwrap();
+    let pr_input = matches.get_one::<String>("pr");
     let repo_input = matches.get_one::<String>("repo");
     let include_resolved = matches.get_flag("include-resolved");
+    let cli_token = matches.get_one::<String>("token");
+    let cli_host = matches.get_one::<String>("host");
+    let host_override = host::host_override(cli_host);
+    let max_comments = matches
+        .get_one::<String>("max-comments")
+        .map(|n| n.parse::<usize>())
+        .transpose()
+        .context("--max-comments must be a number")?;
+
+    // Resolve the repo (and, if given, the PR number) first so the host we
+    // authenticate against always matches where the PR actually lives,
+    // whether that host came from --host/GH_HOST, a PR URL, or the git
+    // remote — not just the explicit-override case.
+    let (mut repo_info, pr_number) = match pr_input {
+        Some(pr_input) => {
+            let (repo_info, pr_number) = parse_input(pr_input, repo_input, host_override.as_ref()).await?;
+            (repo_info, Some(pr_number))
+        }
+        None => {
+            let repo_info = detect_repo_from_git(host_override.as_ref()).await?;
+            (repo_info, None)
+        }
+    };
 
-    let (repo_info, pr_number) = parse_input(pr_input, repo_input).await?;
-    let client = Client::new();
+    let token = auth::resolve_token(cli_token, repo_info.provider.host());
+    let client = GitHubClient::new(token);
+
+    let pr_number = match pr_number {
+        Some(pr_number) => pr_number,
+        None => {
+            let branch = current_branch()?;
+            let (resolved_repo_info, pr_number) =
+                find_pr_for_branch(&client, repo_info, &branch).await?;
+            repo_info = resolved_repo_info;
+            pr_number
+        }
+    };
 
     println!("# PR #{} - {}", pr_number, repo_info.owner);
 
     // Get PR details
     let pr_url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}",
-        repo_info.owner, repo_info.name, pr_number
+        "{}/repos/{}/{}/pulls/{}",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name,
+        pr_number
     );
 
-    let pr: PullRequest = client
-        .get(&pr_url)
-        .header("User-Agent", "gh-pr-comments")
-        .send()
-        .await?
-        .json()
-        .await?;
+    let pr: PullRequest = client.get(&pr_url).await?.json().await?;
 
     println!("**Title:** {}", pr.title);
     println!("**URL:** {}", pr.html_url);
@@ -88,59 +166,167 @@ async fn main() -> Result<()> {
 
     // Get PR comments
     let comments_url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/comments",
-        repo_info.owner, repo_info.name, pr_number
+        "{}/repos/{}/{}/pulls/{}/comments",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name,
+        pr_number
     );
 
-    let comments: Vec<Comment> = client
-        .get(&comments_url)
-        .header("User-Agent", "gh-pr-comments")
-        .send()
-        .await?
-        .json()
+    let comments_page = client
+        .get_all_pages::<Comment>(&comments_url, max_comments)
         .await?;
+    let comments = comments_page.items;
 
-    // Filter comments based on resolved status
-    let filtered_comments: Vec<&Comment> = if include_resolved {
-        comments.iter().collect()
-    } else {
-        // For now, we'll include all comments since GitHub API doesn't directly expose resolved status
-        // In a real implementation, you'd need to check the review conversations API
-        comments.iter().collect()
-    };
+    println!(
+        "**Comments fetched:** {}{}",
+        comments.len(),
+        if comments_page.truncated {
+            " (more available; raise or drop --max-comments to see them)"
+        } else {
+            " (all available)"
+        }
+    );
+    println!();
 
-    println!("## Comments\n");
+    // Resolve each comment's review thread via GraphQL so we can filter out
+    // resolved threads (unless asked to keep them) and group replies under
+    // their root comment instead of flattening everything.
+    let graphql_url = repo_info.provider.graphql_url();
+    let threads =
+        graphql::fetch_review_threads(&client, &graphql_url, &repo_info.owner, &repo_info.name, pr_number)
+            .await?;
+    let thread_status = graphql::index_by_comment_id(&threads);
 
-    for comment in filtered_comments {
-        println!("### Comment by @{}", comment.user.login);
-        println!("**File:** `{}`", comment.path);
-        if let Some(line) = comment.line {
-            println!("**Line:** {}", line);
+    let visible_comments: Vec<&Comment> = comments
+        .iter()
+        .filter(|comment| match thread_status.get(&comment.id) {
+            Some(status) => include_resolved || !status.is_resolved,
+            // No matching thread (e.g. a comment GraphQL didn't return yet): keep it.
+            None => true,
+        })
+        .collect();
+
+    // The general PR discussion and each review's summary/verdict round out
+    // the conversation that inline diff comments alone don't capture. They
+    // respect --max-comments too, so the whole timeline stays within the
+    // bound the flag promises for LLM context windows: each fetch is capped
+    // at whatever the earlier ones left of the budget.
+    let remaining = max_comments.map(|max| max.saturating_sub(comments.len()));
+    let discussion_url = format!(
+        "{}/repos/{}/{}/issues/{}/comments",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name,
+        pr_number
+    );
+    let discussion_comments = client
+        .get_all_pages::<IssueComment>(&discussion_url, remaining)
+        .await?
+        .items;
+    let remaining = remaining.map(|left| left.saturating_sub(discussion_comments.len()));
+
+    let reviews_url = format!(
+        "{}/repos/{}/{}/pulls/{}/reviews",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name,
+        pr_number
+    );
+    let reviews = client
+        .get_all_pages::<Review>(&reviews_url, remaining)
+        .await?
+        .items;
+
+    println!("## Timeline\n");
+    print!(
+        "{}",
+        timeline::render(&visible_comments, &thread_status, &discussion_comments, &reviews)
+    );
+
+    Ok(())
+}
+
+/// Shorthand name of the branch currently checked out, e.g. `main` or
+/// `my-feature`.
+fn current_branch() -> Result<String> {
+    let git_repo = Repository::open(".")?;
+    let head = git_repo.head()?;
+    head.shorthand()
+        .context("Could not determine current branch (detached HEAD?)")
+        .map(|s| s.to_string())
+}
+
+/// Find the open PR associated with `branch`: first by looking for a PR
+/// opened against `repo_info`, then, if it's a fork, by looking for one
+/// opened against its parent (the common "PR from a fork branch" case).
+async fn find_pr_for_branch(
+    client: &GitHubClient,
+    repo_info: RepoInfo,
+    branch: &str,
+) -> Result<(RepoInfo, u32)> {
+    if let Some(pr_number) =
+        find_open_pr_for_head(client, &repo_info, &repo_info.owner, branch).await?
+    {
+        return Ok((repo_info, pr_number));
+    }
+
+    let repo_url = format!(
+        "{}/repos/{}/{}",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name
+    );
+    let repo_details: RepoDetails = client.get(&repo_url).await?.json().await?;
+
+    if let Some(parent) = repo_details.parent {
+        if let Some((parent_owner, parent_name)) = parent.full_name.split_once('/') {
+            let parent_info = RepoInfo {
+                owner: parent_owner.to_string(),
+                name: parent_name.to_string(),
+                provider: host::provider_for_host(repo_info.provider.host()),
+            };
+            if let Some(pr_number) =
+                find_open_pr_for_head(client, &parent_info, &repo_info.owner, branch).await?
+            {
+                return Ok((parent_info, pr_number));
+            }
         }
-        println!("**Created:** {}", comment.created_at);
-        println!("**URL:** {}", comment.html_url);
-        println!();
-
-        println!("#### Diff Context");
-        println!("```diff");
-        println!("{}", comment.diff_hunk);
-        println!("```");
-        println!();
-
-        println!("#### Comment");
-        println!("{}", comment.body);
-        println!();
-        println!("---");
-        println!();
     }
 
-    Ok(())
+    Err(anyhow::anyhow!(
+        "No open PR found for branch '{}'",
+        branch
+    ))
 }
 
-async fn parse_input(pr_input: &str, repo_input: Option<&String>) -> Result<(RepoInfo, u32)> {
+/// Query `repo_info` for an open PR whose head is `head_owner:branch`.
+async fn find_open_pr_for_head(
+    client: &GitHubClient,
+    repo_info: &RepoInfo,
+    head_owner: &str,
+    branch: &str,
+) -> Result<Option<u32>> {
+    let url = format!(
+        "{}/repos/{}/{}/pulls?head={}:{}&state=open",
+        repo_info.provider.api_base_url(),
+        repo_info.owner,
+        repo_info.name,
+        head_owner,
+        branch
+    );
+    let pulls: Vec<PullRequestSummary> = client.get(&url).await?.json().await?;
+    Ok(pulls.into_iter().next().map(|pr| pr.number))
+}
+
+async fn parse_input(
+    pr_input: &str,
+    repo_input: Option<&String>,
+    host_override: Option<&String>,
+) -> Result<(RepoInfo, u32)> {
     // Try to parse as URL first
     if let Ok(url) = Url::parse(pr_input) {
-        return parse_github_url(&url);
+        return parse_github_url(&url, host_override);
     }
 
     // Try to parse as owner/repo/pull/number format
@@ -155,7 +341,9 @@ async fn parse_input(pr_input: &str, repo_input: Option<&String>) -> Result<(Rep
                 let owner = parts[0].to_string();
                 let name = parts[1].to_string();
                 let pr_number = parts[3].parse::<u32>()?;
-                return Ok((RepoInfo { owner, name }, pr_number));
+                let host = host_override.cloned().unwrap_or_else(|| "github.com".to_string());
+                let provider = host::provider_for_host(&host);
+                return Ok((RepoInfo { owner, name, provider }, pr_number));
             }
         }
     }
@@ -163,11 +351,11 @@ async fn parse_input(pr_input: &str, repo_input: Option<&String>) -> Result<(Rep
     // Try to parse as just PR number
     if let Ok(pr_number) = pr_input.parse::<u32>() {
         if let Some(repo) = repo_input {
-            let repo_info = parse_repo_string(repo)?;
+            let repo_info = parse_repo_string(repo, host_override)?;
             return Ok((repo_info, pr_number));
         } else {
             // Try to detect repo from git
-            let repo_info = detect_repo_from_git().await?;
+            let repo_info = detect_repo_from_git(host_override).await?;
             return Ok((repo_info, pr_number));
         }
     }
@@ -175,7 +363,7 @@ async fn parse_input(pr_input: &str, repo_input: Option<&String>) -> Result<(Rep
     Err(anyhow::anyhow!("Could not parse PR input: {}", pr_input))
 }
 
-fn parse_github_url(url: &Url) -> Result<(RepoInfo, u32)> {
+fn parse_github_url(url: &Url, host_override: Option<&String>) -> Result<(RepoInfo, u32)> {
     let path = url.path();
     let re = Regex::new(r"^/([^/]+)/([^/]+)/pull/(\d+)").unwrap();
 
@@ -183,19 +371,25 @@ fn parse_github_url(url: &Url) -> Result<(RepoInfo, u32)> {
         let owner = captures[1].to_string();
         let name = captures[2].to_string();
         let pr_number = captures[3].parse::<u32>()?;
+        let host = host_override
+            .cloned()
+            .unwrap_or_else(|| url.host_str().unwrap_or("github.com").to_string());
+        let provider = host::provider_for_host(&host);
 
-        Ok((RepoInfo { owner, name }, pr_number))
+        Ok((RepoInfo { owner, name, provider }, pr_number))
     } else {
         Err(anyhow::anyhow!("Invalid GitHub PR URL format"))
     }
 }
 
-fn parse_repo_string(repo: &str) -> Result<RepoInfo> {
+fn parse_repo_string(repo: &str, host_override: Option<&String>) -> Result<RepoInfo> {
     let parts: Vec<&str> = repo.split('/').collect();
     if parts.len() == 2 {
+        let host = host_override.cloned().unwrap_or_else(|| "github.com".to_string());
         Ok(RepoInfo {
             owner: parts[0].to_string(),
             name: parts[1].to_string(),
+            provider: host::provider_for_host(&host),
         })
     } else {
         Err(anyhow::anyhow!(
@@ -204,22 +398,43 @@ fn parse_repo_string(repo: &str) -> Result<RepoInfo> {
     }
 }
 
-async fn detect_repo_from_git() -> Result<RepoInfo> {
+async fn detect_repo_from_git(host_override: Option<&String>) -> Result<RepoInfo> {
     let repo = Repository::open(".")?;
     let remote = repo.find_remote("origin")?;
     let url = remote.url().context("No URL for origin remote")?;
 
-    // Parse GitHub URL from git remote
-    let github_re = Regex::new(r"github\.com[:/]([^/]+)/([^/]+?)(?:\.git)?$").unwrap();
+    let (remote_host, owner, name) = parse_remote_url(url).ok_or_else(|| {
+        anyhow::anyhow!("Could not parse GitHub repo from git remote: {}", url)
+    })?;
 
-    if let Some(captures) = github_re.captures(url) {
-        let owner = captures[1].to_string();
-        let name = captures[2].to_string();
-        Ok(RepoInfo { owner, name })
-    } else {
-        Err(anyhow::anyhow!(
-            "Could not parse GitHub repo from git remote: {}",
-            url
-        ))
+    let host = host_override.cloned().unwrap_or(remote_host);
+    Ok(RepoInfo {
+        owner,
+        name,
+        provider: host::provider_for_host(&host),
+    })
+}
+
+/// Parse `(host, owner, name)` out of either an SSH (`git@host:owner/name.git`)
+/// or HTTPS (`https://host/owner/name.git`) remote URL.
+fn parse_remote_url(url: &str) -> Option<(String, String, String)> {
+    let ssh_re = Regex::new(r"^[^@]+@([^:]+):([^/]+)/([^/]+?)(?:\.git)?$").unwrap();
+    if let Some(captures) = ssh_re.captures(url) {
+        return Some((
+            captures[1].to_string(),
+            captures[2].to_string(),
+            captures[3].to_string(),
+        ));
+    }
+
+    let https_re = Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*://([^/]+)/([^/]+)/([^/]+?)(?:\.git)?$").unwrap();
+    if let Some(captures) = https_re.captures(url) {
+        return Some((
+            captures[1].to_string(),
+            captures[2].to_string(),
+            captures[3].to_string(),
+        ));
     }
+
+    None
 }
@@ -0,0 +1,164 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::graphql::ThreadStatus;
+use crate::{Comment, User};
+
+/// A top-level discussion comment on the PR's `Conversation` tab, from
+/// `/issues/{}/comments` (inline review comments live on a separate
+/// endpoint and are modeled by `Comment`).
+#[derive(Debug, Deserialize)]
+pub(crate) struct IssueComment {
+    body: String,
+    user: User,
+    created_at: String,
+    html_url: String,
+}
+
+/// A review verdict from `/pulls/{}/reviews`: its summary body plus the
+/// approve/request-changes/comment state. `body` is often empty when a
+/// reviewer approves without leaving a summary.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Review {
+    user: User,
+    #[serde(default)]
+    body: String,
+    state: String,
+    submitted_at: Option<String>,
+    html_url: String,
+}
+
+/// One entry in the rendered timeline, paired with the timestamp it
+/// sorts on (an ISO 8601 string, which sorts correctly as plain text).
+struct TimelineEntry {
+    timestamp: String,
+    markdown: String,
+}
+
+/// Build the unified, chronologically sorted markdown timeline: inline
+/// review comments (grouped by thread), discussion comments, and review
+/// verdicts, each marked with the kind of entry it is.
+pub(crate) fn render(
+    visible_comments: &[&Comment],
+    thread_status: &HashMap<u64, ThreadStatus>,
+    discussion_comments: &[IssueComment],
+    reviews: &[Review],
+) -> String {
+    let mut entries = Vec::new();
+    entries.extend(render_inline_threads(visible_comments, thread_status));
+    entries.extend(discussion_comments.iter().map(render_discussion_comment));
+    entries.extend(reviews.iter().map(render_review));
+
+    entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let mut output = String::new();
+    for entry in entries {
+        output.push_str(&entry.markdown);
+    }
+    output
+}
+
+/// Group inline comments by thread (as established by `thread_status`)
+/// and render each thread as a single timeline entry, timestamped by its
+/// root comment, so replies stay attached instead of scattering across
+/// the timeline.
+fn render_inline_threads(
+    visible_comments: &[&Comment],
+    thread_status: &HashMap<u64, ThreadStatus>,
+) -> Vec<TimelineEntry> {
+    let mut threads: HashMap<usize, Vec<&Comment>> = HashMap::new();
+    let mut ungrouped = Vec::new();
+
+    for &comment in visible_comments {
+        match thread_status.get(&comment.id) {
+            Some(status) => threads.entry(status.thread_index).or_default().push(comment),
+            None => ungrouped.push(comment),
+        }
+    }
+
+    let mut entries = Vec::new();
+
+    for mut comments in threads.into_values() {
+        comments.sort_by(|a, b| {
+            let a_pos = thread_status.get(&a.id).map(|s| s.position_in_thread);
+            let b_pos = thread_status.get(&b.id).map(|s| s.position_in_thread);
+            a_pos.cmp(&b_pos)
+        });
+        entries.push(render_inline_thread(&comments, thread_status));
+    }
+
+    for comment in ungrouped {
+        entries.push(render_inline_thread(&[comment], thread_status));
+    }
+
+    entries
+}
+
+fn render_inline_thread(
+    comments: &[&Comment],
+    thread_status: &HashMap<u64, ThreadStatus>,
+) -> TimelineEntry {
+    let root = comments[0];
+    let status = thread_status.get(&root.id);
+
+    let mut markdown = String::new();
+    markdown.push_str(&format!("### [Inline Comment] Thread on `{}`\n", root.path));
+    if let Some(status) = status {
+        if status.is_resolved {
+            markdown.push_str("**Status:** resolved\n");
+        } else if status.is_outdated {
+            markdown.push_str("**Status:** outdated\n");
+        }
+    }
+    markdown.push('\n');
+    markdown.push_str("#### Diff Context\n```diff\n");
+    markdown.push_str(&root.diff_hunk);
+    markdown.push_str("\n```\n\n");
+
+    for comment in comments {
+        markdown.push_str(&format!("#### Comment by @{}\n", comment.user.login));
+        if let Some(line) = comment.line {
+            markdown.push_str(&format!("**Line:** {}\n", line));
+        }
+        markdown.push_str(&format!("**Created:** {}\n", comment.created_at));
+        markdown.push_str(&format!("**URL:** {}\n\n", comment.html_url));
+        markdown.push_str(&comment.body);
+        markdown.push_str("\n\n");
+    }
+    markdown.push_str("---\n\n");
+
+    TimelineEntry {
+        timestamp: root.created_at.clone(),
+        markdown,
+    }
+}
+
+fn render_discussion_comment(comment: &IssueComment) -> TimelineEntry {
+    let markdown = format!(
+        "### [Discussion] Comment by @{}\n**Created:** {}\n**URL:** {}\n\n{}\n\n---\n\n",
+        comment.user.login, comment.created_at, comment.html_url, comment.body
+    );
+    TimelineEntry {
+        timestamp: comment.created_at.clone(),
+        markdown,
+    }
+}
+
+fn render_review(review: &Review) -> TimelineEntry {
+    let mut markdown = format!(
+        "### [Review: {}] by @{}\n**URL:** {}\n\n",
+        review.state, review.user.login, review.html_url
+    );
+    if !review.body.is_empty() {
+        markdown.push_str(&review.body);
+        markdown.push('\n');
+    }
+    markdown.push_str("\n---\n\n");
+
+    TimelineEntry {
+        // Pending reviews have no `submitted_at`; sort them as if submitted
+        // at the epoch so they don't silently disappear from the timeline.
+        timestamp: review.submitted_at.clone().unwrap_or_default(),
+        markdown,
+    }
+}